@@ -1,8 +1,65 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use clap::{Arg, ArgAction, ArgMatches, Command};
 use ion_rs::*;
+use std::collections::HashMap;
 use std::fs::File;
-use std::io::{stdin, stdout, StdinLock, Write};
+use std::io::{self, stdin, stdout, BufWriter, Read, StdinLock, Write};
+use std::thread::sleep;
+use std::time::Duration;
+
+/// How long to wait before retrying a read that found no new bytes at the current end of a
+/// followed file, in case more are appended later.
+const FOLLOW_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Wraps a [`File`] being read with `--follow` so that a read which finds nothing at the
+/// current end of the file is retried after a short sleep instead of being reported as the end
+/// of the stream - the file may simply not have grown yet. This is deliberately only used for
+/// regular files: a pipe (e.g. STDIN) already blocks on `read` until its writer produces more
+/// data or closes it, so a plain, unwrapped read already gives exactly the right behavior (and
+/// correctly reports the real end of stream once the writer closes, rather than waiting
+/// forever).
+struct FollowedFile {
+    file: File,
+}
+
+impl Read for FollowedFile {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            let n = self.file.read(buf)?;
+            if n > 0 {
+                return Ok(n);
+            }
+            sleep(FOLLOW_POLL_INTERVAL);
+        }
+    }
+}
+
+/// A `Read` adapter over an in-memory buffer that remembers how many bytes have been handed out
+/// so far. `--continue-on-error` uses this to find out how far a `Reader` got into the buffer
+/// before it failed, so it can restart decoding from that byte offset with a fresh `Reader`
+/// rather than depending on the failed one to have already skipped past the bad bytes on its
+/// own - see [`write_all_values_continuing_on_error`].
+struct CountingReader<'a> {
+    remaining: &'a [u8],
+    consumed: usize,
+}
+
+impl<'a> CountingReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        CountingReader {
+            remaining: bytes,
+            consumed: 0,
+        }
+    }
+}
+
+impl<'a> Read for CountingReader<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.remaining.read(buf)?;
+        self.consumed += n;
+        Ok(n)
+    }
+}
 
 pub fn app() -> Command {
     Command::new("dump")
@@ -12,7 +69,7 @@ pub fn app() -> Command {
                 .long("format")
                 .short('f')
                 .default_value("pretty")
-                .value_parser(["binary", "text", "pretty", "lines"])
+                .value_parser(["binary", "text", "pretty", "lines", "json"])
                 .help("Output format"),
         )
         .arg(
@@ -21,6 +78,64 @@ pub fn app() -> Command {
                 .short('o')
                 .help("Output file [default: STDOUT]"),
         )
+        .arg(
+            Arg::new("json-array")
+                .long("json-array")
+                .action(ArgAction::SetTrue)
+                .help("With `--format json`, wrap all top-level values in a single JSON array instead of emitting newline-delimited JSON"),
+        )
+        .arg(
+            Arg::new("annotations-as")
+                .long("annotations-as")
+                .help("With `--format json`, record each value's annotations in a sidecar object field with this name instead of dropping them"),
+        )
+        .arg(
+            Arg::new("catalog")
+                .long("catalog")
+                .action(ArgAction::Append)
+                .help("File containing one or more Ion shared symbol table definitions. May be repeated. The resulting catalog is used to resolve imported symbol IDs when reading, and to supply tables for `--import` when writing"),
+        )
+        .arg(
+            Arg::new("import")
+                .long("import")
+                .action(ArgAction::Append)
+                .value_name("name@version")
+                .help("With `--format binary`, import the named shared symbol table (found via `--catalog`) into the output's local symbol table instead of inlining its symbols. May be repeated"),
+        )
+        .arg(
+            Arg::new("follow")
+                .long("follow")
+                .action(ArgAction::SetTrue)
+                .help("Keep reading the input as more data arrives (tail -f style), transcoding each top-level value as soon as it finishes instead of stopping at the current end of input. Only valid with a single input file or STDIN"),
+        )
+        .arg(
+            Arg::new("flush-every")
+                .long("flush-every")
+                .default_value("100")
+                .value_parser(clap::value_parser!(usize))
+                .help("Flush the output after every N top-level values written; 0 flushes only once, at the end"),
+        )
+        .arg(
+            Arg::new("buffer-size")
+                .long("buffer-size")
+                .default_value("8192")
+                .value_parser(clap::value_parser!(usize))
+                .help("Size in bytes of the output buffer"),
+        )
+        .arg(
+            Arg::new("stats")
+                .long("stats")
+                .action(ArgAction::SetTrue)
+                .conflicts_with("follow")
+                .help("Instead of transcoding the input, walk it and print a summary: value counts by type, max nesting depth, symbol/field-name frequency, and min/max/mean of numeric scalars"),
+        )
+        .arg(
+            Arg::new("continue-on-error")
+                .long("continue-on-error")
+                .action(ArgAction::SetTrue)
+                .conflicts_with("follow")
+                .help("Read one top-level value at a time; on a malformed value, log a diagnostic to stderr and resynchronize at the next top-level value instead of aborting the whole dump"),
+        )
         .arg(
             // All argv entries after the program name (argv[0])
             // and any `clap`-managed options are considered input files.
@@ -38,9 +153,36 @@ pub fn run(_command_name: &str, matches: &ArgMatches) -> Result<()> {
     let format = matches
         .get_one::<String>("format")
         .expect("`format` did not have a value");
+    let json_array = matches.get_flag("json-array");
+    let annotations_as = matches
+        .get_one::<String>("annotations-as")
+        .map(String::as_str);
+
+    // --catalog path...
+    let catalog = load_catalog(matches.get_many::<String>("catalog"))?;
+    // --import name@version...
+    let imports = resolve_imports(&catalog, matches.get_many::<String>("import"))?;
+    // --follow
+    let follow = matches.get_flag("follow");
+    // --stats
+    let stats = matches.get_flag("stats");
+    // --flush-every, --buffer-size
+    let flush_every = *matches
+        .get_one::<usize>("flush-every")
+        .expect("`flush-every` did not have a value");
+    // `--follow`'s whole point is that a consumer tailing the output sees each value as soon as
+    // it's finalized, so buffering several values (or waiting for EOF, via `--flush-every 0`)
+    // before flushing would defeat it. Force a flush after every value instead of trusting
+    // `--flush-every` to have been set accordingly.
+    let flush_every = if follow { 1 } else { flush_every };
+    let buffer_size = *matches
+        .get_one::<usize>("buffer-size")
+        .expect("`buffer-size` did not have a value");
+    // --continue-on-error
+    let continue_on_error = matches.get_flag("continue-on-error");
 
     // -o filename
-    let mut output: Box<dyn Write> = if let Some(output_file) = matches.get_one::<String>("output")
+    let raw_output: Box<dyn Write> = if let Some(output_file) = matches.get_one::<String>("output")
     {
         let file = File::create(output_file).with_context(|| {
             format!(
@@ -52,124 +194,616 @@ pub fn run(_command_name: &str, matches: &ArgMatches) -> Result<()> {
     } else {
         Box::new(stdout().lock())
     };
+    let mut output: Box<dyn Write> = Box::new(BufWriter::with_capacity(buffer_size, raw_output));
+
+    if stats {
+        let mut report = StreamStats::default();
+        if let Some(input_file_iter) = matches.get_many::<String>("input") {
+            for input_file in input_file_iter {
+                let file = File::open(input_file)
+                    .with_context(|| format!("Could not open file '{}'", input_file))?;
+                let mut reader = ReaderBuilder::new()
+                    .with_catalog(catalog.clone())
+                    .build(file)?;
+                accumulate_stats(&mut reader, &mut report)?;
+            }
+        } else {
+            let input: StdinLock = stdin().lock();
+            let mut reader = ReaderBuilder::new()
+                .with_catalog(catalog.clone())
+                .build(input)?;
+            accumulate_stats(&mut reader, &mut report)?;
+        }
+        write!(output, "{}", report.render())?;
+        output.flush()?;
+        return Ok(());
+    }
+
+    if continue_on_error {
+        // Resynchronizing past a malformed value needs random access into the input (see
+        // `write_all_values_continuing_on_error`), so this path reads everything into memory up
+        // front instead of building a streaming `Reader` like the rest of `run` does. `--follow`
+        // is mutually exclusive with `--continue-on-error` (see the arg definitions), so there's
+        // no followed/unbounded input to worry about slurping here.
+        if let Some(input_file_iter) = matches.get_many::<String>("input") {
+            for input_file in input_file_iter {
+                let bytes = std::fs::read(input_file)
+                    .with_context(|| format!("Could not open file '{}'", input_file))?;
+                write_all_continuing_on_error_in_format(
+                    &bytes,
+                    &mut output,
+                    format,
+                    &imports,
+                    flush_every,
+                )?;
+            }
+        } else {
+            let mut bytes = vec![];
+            stdin()
+                .lock()
+                .read_to_end(&mut bytes)
+                .context("could not read STDIN")?;
+            write_all_continuing_on_error_in_format(
+                &bytes,
+                &mut output,
+                format,
+                &imports,
+                flush_every,
+            )?;
+        }
+        output.flush()?;
+        return Ok(());
+    }
 
     if let Some(input_file_iter) = matches.get_many::<String>("input") {
-        for input_file in input_file_iter {
+        let input_files: Vec<&String> = input_file_iter.collect();
+        if follow && input_files.len() > 1 {
+            bail!("--follow only supports a single input file");
+        }
+        for input_file in input_files {
             let file = File::open(input_file)
                 .with_context(|| format!("Could not open file '{}'", input_file))?;
-            let mut reader = ReaderBuilder::new().build(file)?;
-            write_all_in_format(&mut reader, &mut output, format)?;
+            let mut reader = if follow {
+                ReaderBuilder::new()
+                    .with_catalog(catalog.clone())
+                    .build(FollowedFile { file })?
+            } else {
+                ReaderBuilder::new()
+                    .with_catalog(catalog.clone())
+                    .build(file)?
+            };
+            write_all_in_format(
+                &mut reader,
+                &mut output,
+                format,
+                json_array,
+                annotations_as,
+                &imports,
+                flush_every,
+            )?;
         }
     } else {
+        // STDIN is a pipe, not a regular file: a `read` on it already blocks until its writer
+        // produces more data or closes it, which is exactly the behavior `--follow` wants, so
+        // there's nothing extra to do here - `follow` has no effect reading from STDIN beyond
+        // what a plain read already does.
         let input: StdinLock = stdin().lock();
-        let mut reader = ReaderBuilder::new().build(input)?;
-        write_all_in_format(&mut reader, &mut output, format)?;
+        let mut reader = ReaderBuilder::new()
+            .with_catalog(catalog.clone())
+            .build(input)?;
+        write_all_in_format(
+            &mut reader,
+            &mut output,
+            format,
+            json_array,
+            annotations_as,
+            &imports,
+            flush_every,
+        )?;
     }
 
     output.flush()?;
     Ok(())
 }
 
+/// Builds a `MapCatalog` containing exactly the `--import`ed tables, to hand to a binary writer
+/// the same way a `Reader` is given a catalog to resolve imported symbol IDs: the writer consults
+/// it to recognize that these symbols are already defined by a shared table and should be
+/// referenced via import rather than inlined into the output's local symbol table.
+fn import_catalog(imports: &[SharedSymbolTable]) -> MapCatalog {
+    let mut catalog = MapCatalog::new();
+    for table in imports {
+        catalog.add_symbol_table(table.clone());
+    }
+    catalog
+}
+
 /// Constructs the appropriate writer for the given format, then writes all values found in the
 /// Reader to the new Writer.
+///
+/// `--follow` has no presence here: it's handled entirely by wrapping the input `Read` before
+/// the `Reader` is built (see [`FollowedFile`]), so every format transcodes a followed input the
+/// same way it would transcode a finite one. `--continue-on-error` also has no presence here: it
+/// needs random access into the input to resynchronize past a malformed value, so it's handled by
+/// the separate [`write_all_continuing_on_error_in_format`] instead of this `Reader`-based path.
 fn write_all_in_format(
     reader: &mut Reader,
     output: &mut Box<dyn Write>,
     format: &str,
-) -> IonResult<()> {
+    json_array: bool,
+    annotations_as: Option<&str>,
+    imports: &[SharedSymbolTable],
+    flush_every: usize,
+) -> Result<()> {
     match format {
         "pretty" => {
             let mut writer = TextWriterBuilder::pretty().build(output)?;
-            write_all_values(reader, &mut writer)
+            Ok(write_all_values(reader, &mut writer, flush_every)?)
         }
         "text" => {
             let mut writer = TextWriterBuilder::default().build(output)?;
-            write_all_values(reader, &mut writer)
+            Ok(write_all_values(reader, &mut writer, flush_every)?)
         }
         "lines" => {
             let mut writer = TextWriterBuilder::lines().build(output)?;
-            write_all_values(reader, &mut writer)
+            Ok(write_all_values(reader, &mut writer, flush_every)?)
         }
         "binary" => {
-            let mut writer = BinaryWriterBuilder::new().build(output)?;
-            write_all_values(reader, &mut writer)
+            let mut builder = BinaryWriterBuilder::new();
+            if !imports.is_empty() {
+                builder = builder.with_catalog(import_catalog(imports));
+            }
+            let mut writer = builder.build(output)?;
+            Ok(write_all_values(reader, &mut writer, flush_every)?)
         }
+        "json" => Ok(write_all_as_json(
+            reader,
+            output,
+            json_array,
+            annotations_as,
+            flush_every,
+        )?),
         unrecognized => unreachable!(
-            "'format' was '{}' instead of 'pretty', 'text', 'lines', or 'binary'",
+            "'format' was '{}' instead of 'pretty', 'text', 'lines', 'binary', or 'json'",
             unrecognized
         ),
     }
 }
 
-/// Writes each value encountered in the Reader to the provided IonWriter.
-fn write_all_values<W: IonWriter>(reader: &mut Reader, writer: &mut W) -> IonResult<()> {
-    const FLUSH_EVERY_N: usize = 100;
-    let mut values_since_flush: usize = 0;
-    let mut annotations = vec![];
+/// The `--continue-on-error` counterpart to [`write_all_in_format`]: constructs the appropriate
+/// writer for the given format, then writes every top-level value found in `bytes`, skipping
+/// past any that are malformed instead of aborting. See
+/// [`write_all_values_continuing_on_error`] for why this takes the whole input as a byte slice
+/// rather than a `Reader`. The `json` format is not supported here and returns an error.
+fn write_all_continuing_on_error_in_format(
+    bytes: &[u8],
+    output: &mut Box<dyn Write>,
+    format: &str,
+    imports: &[SharedSymbolTable],
+    flush_every: usize,
+) -> Result<()> {
+    match format {
+        "pretty" => {
+            let mut writer = TextWriterBuilder::pretty().build(output)?;
+            Ok(write_all_values_continuing_on_error(
+                bytes,
+                &mut writer,
+                flush_every,
+            )?)
+        }
+        "text" => {
+            let mut writer = TextWriterBuilder::default().build(output)?;
+            Ok(write_all_values_continuing_on_error(
+                bytes,
+                &mut writer,
+                flush_every,
+            )?)
+        }
+        "lines" => {
+            let mut writer = TextWriterBuilder::lines().build(output)?;
+            Ok(write_all_values_continuing_on_error(
+                bytes,
+                &mut writer,
+                flush_every,
+            )?)
+        }
+        "binary" => {
+            let mut builder = BinaryWriterBuilder::new();
+            if !imports.is_empty() {
+                builder = builder.with_catalog(import_catalog(imports));
+            }
+            let mut writer = builder.build(output)?;
+            Ok(write_all_values_continuing_on_error(
+                bytes,
+                &mut writer,
+                flush_every,
+            )?)
+        }
+        "json" => bail!("--continue-on-error is not yet supported with --format json"),
+        unrecognized => unreachable!(
+            "'format' was '{}' instead of 'pretty', 'text', 'lines', 'binary', or 'json'",
+            unrecognized
+        ),
+    }
+}
+
+/// Reads the shared symbol table definitions out of each `--catalog` file and loads them into a
+/// `MapCatalog`, so that binary readers can resolve symbol IDs imported from those tables and
+/// `--import` can reference them when writing.
+fn load_catalog(paths: Option<clap::parser::ValuesRef<String>>) -> Result<MapCatalog> {
+    let mut catalog = MapCatalog::new();
+    let Some(paths) = paths else {
+        return Ok(catalog);
+    };
+    for path in paths {
+        let file =
+            File::open(path).with_context(|| format!("could not open catalog file '{}'", path))?;
+        let mut reader = ReaderBuilder::new().build(file)?;
+        for table in read_shared_symbol_tables(&mut reader)
+            .with_context(|| format!("while reading shared symbol tables from '{}'", path))?
+        {
+            catalog.add_symbol_table(table);
+        }
+    }
+    Ok(catalog)
+}
+
+/// Reads every top-level `$ion_shared_symbol_table`-annotated struct out of `reader`. Other
+/// top-level values are skipped; a catalog file is free to mix symbol table definitions with
+/// other data, though in practice it usually contains only the former.
+fn read_shared_symbol_tables(reader: &mut Reader) -> IonResult<Vec<SharedSymbolTable>> {
+    let mut tables = vec![];
     loop {
         match reader.next()? {
-            StreamItem::Value(ion_type) | StreamItem::Null(ion_type) => {
-                if reader.has_annotations() {
-                    annotations.clear();
-                    for annotation in reader.annotations() {
-                        annotations.push(annotation?);
-                    }
-                    writer.set_annotations(&annotations);
+            StreamItem::Value(IonType::Struct) => {
+                let is_shared_symbol_table = reader
+                    .annotations()
+                    .filter_map(|a| a.ok())
+                    .any(|a| a.text() == Some("$ion_shared_symbol_table"));
+                reader.step_in()?;
+                if is_shared_symbol_table {
+                    tables.push(read_shared_symbol_table_fields(reader)?);
+                } else {
+                    while reader.next()? != StreamItem::Nothing {}
                 }
+                reader.step_out()?;
+            }
+            StreamItem::Value(_) | StreamItem::Null(_) => {}
+            StreamItem::Nothing => break,
+        }
+    }
+    Ok(tables)
+}
 
-                if reader.parent_type() == Some(IonType::Struct) {
-                    writer.set_field_name(reader.field_name()?);
+/// Reads the `name`, `version`, and `symbols` fields of a shared symbol table struct whose
+/// reader has already been stepped into.
+fn read_shared_symbol_table_fields(reader: &mut Reader) -> IonResult<SharedSymbolTable> {
+    let mut name = None;
+    let mut version = 1usize;
+    let mut symbols = vec![];
+    loop {
+        match reader.next()? {
+            StreamItem::Value(ion_type) => match (reader.field_name()?.text(), ion_type) {
+                (Some("name"), IonType::String) => name = Some(reader.read_string()?.to_string()),
+                (Some("version"), IonType::Integer) => {
+                    version = reader.read_integer()?.as_i64().unwrap_or(1).max(1) as usize
+                }
+                (Some("symbols"), IonType::List) => {
+                    reader.step_in()?;
+                    loop {
+                        match reader.next()? {
+                            StreamItem::Value(IonType::String) => {
+                                symbols.push(Some(reader.read_string()?.to_string()))
+                            }
+                            StreamItem::Null(_) => symbols.push(None),
+                            StreamItem::Nothing => break,
+                            _ => {}
+                        }
+                    }
+                    reader.step_out()?;
                 }
+                _ => {}
+            },
+            StreamItem::Null(_) => {}
+            StreamItem::Nothing => break,
+        }
+    }
+    let name = name.ok_or_else(|| {
+        IonError::decoding_error("shared symbol table definition is missing its 'name' field")
+    })?;
+    SharedSymbolTable::new(name, version, symbols)
+}
 
-                if reader.is_null() {
-                    writer.write_null(ion_type)?;
-                    continue;
+/// Parses each `name@version` selector passed via `--import` and resolves it against `catalog`,
+/// preserving the order the selectors were given in so the output's imports list is
+/// deterministic.
+fn resolve_imports(
+    catalog: &MapCatalog,
+    selectors: Option<clap::parser::ValuesRef<String>>,
+) -> Result<Vec<SharedSymbolTable>> {
+    let Some(selectors) = selectors else {
+        return Ok(vec![]);
+    };
+    let mut imports = vec![];
+    for selector in selectors {
+        let (name, version) = selector.rsplit_once('@').with_context(|| {
+            format!(
+                "invalid --import selector '{}'; expected '<name>@<version>'",
+                selector
+            )
+        })?;
+        let version: usize = version
+            .parse()
+            .with_context(|| format!("invalid version in --import selector '{}'", selector))?;
+        let table = catalog
+            .get_table_by_version(name, version)
+            .with_context(|| {
+                format!(
+                    "no shared symbol table '{}@{}' found via --catalog",
+                    name, version
+                )
+            })?;
+        imports.push(table.clone());
+    }
+    Ok(imports)
+}
+
+#[cfg(test)]
+mod catalog_tests {
+    use super::*;
+
+    #[test]
+    fn read_shared_symbol_tables_parses_name_version_and_symbols() {
+        let ion_text =
+            r#"$ion_shared_symbol_table::{name:"my_table", version:2, symbols:["foo","bar"]}"#;
+        let mut reader = ReaderBuilder::new().build(ion_text.as_bytes()).unwrap();
+        let tables = read_shared_symbol_tables(&mut reader).unwrap();
+        assert_eq!(tables.len(), 1);
+        assert_eq!(tables[0].name(), "my_table");
+        assert_eq!(tables[0].version(), 2);
+    }
+
+    #[test]
+    fn read_shared_symbol_tables_skips_values_without_the_annotation() {
+        let ion_text = r#"
+            {a: 1}
+            $ion_shared_symbol_table::{name:"t", version:1, symbols:[]}
+            "not a shared symbol table"
+        "#;
+        let mut reader = ReaderBuilder::new().build(ion_text.as_bytes()).unwrap();
+        let tables = read_shared_symbol_tables(&mut reader).unwrap();
+        assert_eq!(tables.len(), 1);
+        assert_eq!(tables[0].name(), "t");
+    }
+
+    #[test]
+    fn write_all_in_format_binary_accepts_imported_tables() {
+        let imports = vec![SharedSymbolTable::new(
+            "my_table".to_string(),
+            1,
+            vec![Some("greeting".to_string())],
+        )
+        .unwrap()];
+        let mut reader = ReaderBuilder::new().build("\"hello\"".as_bytes()).unwrap();
+        let mut bytes: Vec<u8> = Vec::new();
+        {
+            let mut boxed: Box<dyn Write> = Box::new(&mut bytes);
+            write_all_in_format(
+                &mut reader,
+                &mut boxed,
+                "binary",
+                false,
+                None,
+                &imports,
+                100,
+            )
+            .unwrap();
+        }
+        assert!(!bytes.is_empty());
+    }
+}
+
+/// Running min/max/mean over the numeric scalars (integer, float, decimal) seen in a stream.
+#[derive(Default)]
+struct NumericStats {
+    count: u64,
+    sum: f64,
+    min: f64,
+    max: f64,
+}
+
+impl NumericStats {
+    fn observe(&mut self, value: f64) {
+        if self.count == 0 {
+            self.min = value;
+            self.max = value;
+        } else {
+            self.min = self.min.min(value);
+            self.max = self.max.max(value);
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+
+    fn mean(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            self.sum / self.count as f64
+        }
+    }
+}
+
+/// Accumulated by [`accumulate_stats`] and rendered by [`StreamStats::render`] for `--stats`.
+#[derive(Default)]
+struct StreamStats {
+    top_level_values: usize,
+    type_counts: HashMap<IonType, usize>,
+    max_depth: usize,
+    symbol_occurrences: HashMap<String, usize>,
+    numeric: NumericStats,
+}
+
+impl StreamStats {
+    fn render(&self) -> String {
+        let mut out = String::new();
+        out.push_str(&format!("top-level values:  {}\n", self.top_level_values));
+        out.push_str(&format!("max nesting depth: {}\n", self.max_depth));
+
+        out.push_str("\nvalue counts by type:\n");
+        let mut type_counts: Vec<(&IonType, &usize)> = self.type_counts.iter().collect();
+        type_counts.sort_by(|a, b| {
+            b.1.cmp(a.1)
+                .then_with(|| format!("{:?}", a.0).cmp(&format!("{:?}", b.0)))
+        });
+        for (ion_type, count) in type_counts {
+            out.push_str(&format!("  {:?}: {}\n", ion_type, count));
+        }
+
+        out.push_str("\nmost common symbols/field names:\n");
+        let mut symbols: Vec<(&String, &usize)> = self.symbol_occurrences.iter().collect();
+        symbols.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        for (text, count) in symbols.into_iter().take(10) {
+            out.push_str(&format!("  {:?}: {}\n", text, count));
+        }
+
+        out.push_str("\nnumeric scalars (integer, float, decimal):\n");
+        if self.numeric.count == 0 {
+            out.push_str("  (none)\n");
+        } else {
+            out.push_str(&format!(
+                "  count: {}, min: {}, max: {}, mean: {}\n",
+                self.numeric.count,
+                self.numeric.min,
+                self.numeric.max,
+                self.numeric.mean()
+            ));
+        }
+        out
+    }
+}
+
+/// Walks every value in `reader`, recording counts and statistics into `stats` instead of
+/// writing anything back out. Reuses the same `reader.next()` / `step_in` / `step_out`
+/// traversal as [`write_all_values`], just with an accumulator in place of a writer.
+fn accumulate_stats(reader: &mut Reader, stats: &mut StreamStats) -> IonResult<()> {
+    loop {
+        match reader.next()? {
+            StreamItem::Value(ion_type) | StreamItem::Null(ion_type) => {
+                *stats.type_counts.entry(ion_type).or_insert(0) += 1;
+
+                if reader.parent_type() == Some(IonType::Struct) {
+                    if let Some(text) = reader.field_name()?.text() {
+                        *stats
+                            .symbol_occurrences
+                            .entry(text.to_string())
+                            .or_insert(0) += 1;
+                    }
                 }
 
-                use IonType::*;
-                match ion_type {
-                    Null => unreachable!("null values are handled prior to this match"),
-                    Boolean => writer.write_bool(reader.read_bool()?)?,
-                    Integer => writer.write_integer(&reader.read_integer()?)?,
-                    Float => {
-                        let float64 = reader.read_f64()?;
-                        let float32 = float64 as f32;
-                        if float32 as f64 == float64 {
-                            // No data lost during cast; write it as an f32
-                            writer.write_f32(float32)?;
-                        } else {
-                            writer.write_f64(float64)?;
+                if !reader.is_null() {
+                    use IonType::*;
+                    match ion_type {
+                        Integer => {
+                            // Go through the integer's own (arbitrary-precision) string form
+                            // rather than `as_i64()`, which is `None` for integers that overflow
+                            // i64, so those still show up in the numeric summary instead of being
+                            // silently dropped.
+                            // This is still ultimately an f64 aggregate, so very large magnitudes
+                            // lose precision, but they're no longer excluded outright.
+                            if let Ok(i) = reader.read_integer()?.to_string().parse::<f64>() {
+                                stats.numeric.observe(i);
+                            }
                         }
-                    }
-                    Decimal => writer.write_decimal(&reader.read_decimal()?)?,
-                    Timestamp => writer.write_timestamp(&reader.read_timestamp()?)?,
-                    Symbol => writer.write_symbol(reader.read_symbol()?)?,
-                    String => writer.write_string(reader.read_string()?)?,
-                    Clob => writer.write_clob(reader.read_clob()?)?,
-                    Blob => writer.write_blob(reader.read_blob()?)?,
-                    List => {
-                        reader.step_in()?;
-                        writer.step_in(List)?;
-                    }
-                    SExpression => {
-                        reader.step_in()?;
-                        writer.step_in(SExpression)?;
-                    }
-                    Struct => {
-                        reader.step_in()?;
-                        writer.step_in(Struct)?;
+                        Float => stats.numeric.observe(reader.read_f64()?),
+                        Decimal => {
+                            if let Ok(d) = reader.read_decimal()?.to_string().parse::<f64>() {
+                                stats.numeric.observe(d);
+                            }
+                        }
+                        Symbol => {
+                            if let Some(text) = reader.read_symbol()?.text() {
+                                *stats
+                                    .symbol_occurrences
+                                    .entry(text.to_string())
+                                    .or_insert(0) += 1;
+                            }
+                        }
+                        List | SExpression | Struct => {
+                            reader.step_in()?;
+                            stats.max_depth = stats.max_depth.max(reader.depth());
+                        }
+                        _ => {}
                     }
                 }
             }
+            StreamItem::Nothing if reader.depth() > 0 => {
+                reader.step_out()?;
+            }
+            StreamItem::Nothing => break,
+        }
+        if reader.depth() == 0 {
+            stats.top_level_values += 1;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod stats_tests {
+    use super::*;
+
+    #[test]
+    fn accumulate_stats_counts_top_level_nulls() {
+        let mut reader = ReaderBuilder::new()
+            .build("1 null null.string 2".as_bytes())
+            .unwrap();
+        let mut stats = StreamStats::default();
+        accumulate_stats(&mut reader, &mut stats).unwrap();
+        assert_eq!(stats.top_level_values, 4);
+        assert_eq!(stats.numeric.count, 2);
+    }
+
+    #[test]
+    fn accumulate_stats_includes_integers_that_overflow_i64() {
+        // One larger than i64::MAX, so `as_i64()` returns `None` for it.
+        let mut reader = ReaderBuilder::new()
+            .build("9223372036854775808".as_bytes())
+            .unwrap();
+        let mut stats = StreamStats::default();
+        accumulate_stats(&mut reader, &mut stats).unwrap();
+        assert_eq!(stats.top_level_values, 1);
+        assert_eq!(stats.numeric.count, 1);
+    }
+}
+
+/// Writes each value encountered in the Reader to the provided IonWriter.
+///
+/// `--follow` doesn't appear here at all: when following, `reader` was built over a
+/// [`FollowedFile`], whose `read` blocks and retries instead of reporting a premature end of
+/// stream, so `reader.next()` itself already waits for more data to arrive at a top-level
+/// boundary. That keeps this loop identical whether or not `--follow` was requested.
+///
+/// `flush_every` is the `--flush-every` policy: the writer is flushed after every `flush_every`
+/// top-level values, or only once at the end (after the loop) if it's `0`.
+fn write_all_values<W: IonWriter>(
+    reader: &mut Reader,
+    writer: &mut W,
+    flush_every: usize,
+) -> IonResult<()> {
+    let mut values_since_flush: usize = 0;
+    loop {
+        match reader.next()? {
             StreamItem::Nothing if reader.depth() > 0 => {
                 reader.step_out()?;
                 writer.step_out()?;
             }
             StreamItem::Nothing => break,
+            item => write_stream_item(reader, writer, item)?,
         }
         if reader.depth() == 0 {
             values_since_flush += 1;
-            if values_since_flush == FLUSH_EVERY_N {
+            if flush_every != 0 && values_since_flush == flush_every {
                 writer.flush()?;
                 values_since_flush = 0;
             }
@@ -178,3 +812,492 @@ fn write_all_values<W: IonWriter>(reader: &mut Reader, writer: &mut W) -> IonRes
     writer.flush()?;
     Ok(())
 }
+
+/// Writes a single `StreamItem::Value`/`StreamItem::Null` to `writer`, stepping both `reader`
+/// and `writer` into the value's contents if it's a container. Shared by [`write_all_values`]
+/// and the `--continue-on-error` path in [`write_all_values_continuing_on_error`].
+fn write_stream_item<W: IonWriter>(
+    reader: &mut Reader,
+    writer: &mut W,
+    item: StreamItem,
+) -> IonResult<()> {
+    let (StreamItem::Value(ion_type) | StreamItem::Null(ion_type)) = item else {
+        unreachable!("write_stream_item is only called with StreamItem::Value or StreamItem::Null");
+    };
+
+    if reader.has_annotations() {
+        let mut annotations = vec![];
+        for annotation in reader.annotations() {
+            annotations.push(annotation?);
+        }
+        writer.set_annotations(&annotations);
+    }
+
+    if reader.parent_type() == Some(IonType::Struct) {
+        writer.set_field_name(reader.field_name()?);
+    }
+
+    if reader.is_null() {
+        writer.write_null(ion_type)?;
+        return Ok(());
+    }
+
+    use IonType::*;
+    match ion_type {
+        Null => unreachable!("null values are handled prior to this match"),
+        Boolean => writer.write_bool(reader.read_bool()?)?,
+        Integer => writer.write_integer(&reader.read_integer()?)?,
+        Float => {
+            let float64 = reader.read_f64()?;
+            let float32 = float64 as f32;
+            if float32 as f64 == float64 {
+                // No data lost during cast; write it as an f32
+                writer.write_f32(float32)?;
+            } else {
+                writer.write_f64(float64)?;
+            }
+        }
+        Decimal => writer.write_decimal(&reader.read_decimal()?)?,
+        Timestamp => writer.write_timestamp(&reader.read_timestamp()?)?,
+        Symbol => writer.write_symbol(reader.read_symbol()?)?,
+        String => writer.write_string(reader.read_string()?)?,
+        Clob => writer.write_clob(reader.read_clob()?)?,
+        Blob => writer.write_blob(reader.read_blob()?)?,
+        List => {
+            reader.step_in()?;
+            writer.step_in(List)?;
+        }
+        SExpression => {
+            reader.step_in()?;
+            writer.step_in(SExpression)?;
+        }
+        Struct => {
+            reader.step_in()?;
+            writer.step_in(Struct)?;
+        }
+    }
+    Ok(())
+}
+
+/// Like [`write_all_values`], but recovers from a malformed top-level value instead of aborting
+/// the whole transcode.
+///
+/// This needs to know exactly how many bytes a `Reader` got through before it failed, so that it
+/// can restart decoding right after them - something a streaming `Reader` over a generic `Read`
+/// doesn't expose. So unlike every other write path in this file, `--continue-on-error` operates
+/// on the whole input loaded into memory up front (see the `continue_on_error` branch in `run`)
+/// rather than a single long-lived `Reader`: each iteration wraps the *remaining* bytes in a
+/// [`CountingReader`], builds a fresh `Reader` over it, and reads one top-level value with
+/// [`ElementReader::read_one_element`].
+///
+/// On success, `CountingReader::consumed` tells us exactly how many bytes that value took, and
+/// `bytes` is advanced by that much for the next iteration. On failure, it tells us how far the
+/// failed `Reader` got before giving up; we skip past that much of the input and try again there.
+/// If a failure is reported without having consumed anything at all (so there's nothing to skip
+/// past), we force a single byte of progress instead - that guarantees the loop always makes
+/// headway and can never spin on the same bad byte forever.
+fn write_all_values_continuing_on_error<W: IonWriter + ElementWriter>(
+    mut bytes: &[u8],
+    writer: &mut W,
+    flush_every: usize,
+) -> IonResult<()> {
+    let mut values_since_flush: usize = 0;
+    let mut skipped = 0usize;
+
+    while !bytes.is_empty() {
+        let mut counting = CountingReader::new(bytes);
+        let result = ReaderBuilder::new()
+            .build(&mut counting)
+            .and_then(|mut reader| reader.read_one_element());
+
+        match result {
+            Ok(None) => break,
+            Ok(Some(element)) => {
+                writer.write_element(&element)?;
+                bytes = &bytes[counting.consumed.clamp(1, bytes.len())..];
+                values_since_flush += 1;
+                if flush_every != 0 && values_since_flush == flush_every {
+                    writer.flush()?;
+                    values_since_flush = 0;
+                }
+            }
+            Err(e) => {
+                report_skipped_value(&e, &mut skipped);
+                bytes = &bytes[counting.consumed.clamp(1, bytes.len())..];
+            }
+        }
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Logs a `--continue-on-error` diagnostic for a value that couldn't be read or written.
+fn report_skipped_value(error: &IonError, skipped: &mut usize) {
+    *skipped += 1;
+    eprintln!(
+        "ion dump: skipping malformed value: {} ({} value{} skipped so far)",
+        error,
+        skipped,
+        if *skipped == 1 { "" } else { "s" }
+    );
+}
+
+#[cfg(test)]
+mod continue_on_error_tests {
+    use super::*;
+
+    #[test]
+    fn write_all_continuing_on_error_in_format_keeps_good_values_around_a_bad_one() {
+        // A well-formed `1`, then a token that isn't valid Ion, then a well-formed `2`.
+        let ion = b"1 @#$%^&* 2";
+        let mut bytes: Vec<u8> = Vec::new();
+        {
+            let mut boxed: Box<dyn Write> = Box::new(&mut bytes);
+            write_all_continuing_on_error_in_format(ion, &mut boxed, "text", &[], 0).unwrap();
+        }
+        let output = String::from_utf8(bytes).unwrap();
+        let values: Vec<&str> = output.split_whitespace().collect();
+        assert_eq!(values, vec!["1", "2"]);
+    }
+}
+
+/// Marks the kind of container a `]`/`}` closing bracket belongs to, so
+/// [`write_all_as_json`] knows which character to emit when it steps back out.
+enum JsonContainer {
+    List,
+    Struct,
+}
+
+/// Down-converts every value in `reader` into JSON and writes it to `output`.
+///
+/// Ion is a richer type system than JSON, so this is a lossy conversion: annotations are
+/// dropped (unless `annotations_as` names a sidecar field to stash them in), and symbols and
+/// strings both become JSON strings. Because Ion is a stream of top-level values rather than a
+/// single document, and because Ion structs may contain duplicate field names, values are
+/// written as newline-delimited JSON by default; `json_array` wraps them in a single array
+/// instead. Duplicate struct fields are emitted as repeated JSON object keys, which is valid
+/// JSON and is resolved by most parsers with last-value-wins semantics.
+fn write_all_as_json(
+    reader: &mut Reader,
+    output: &mut Box<dyn Write>,
+    json_array: bool,
+    annotations_as: Option<&str>,
+    flush_every: usize,
+) -> IonResult<()> {
+    let mut containers: Vec<JsonContainer> = vec![];
+    // Tracks, for the innermost open container (or the top level), whether we've already
+    // written a value and therefore need a separating comma before the next one.
+    let mut is_first: Vec<bool> = vec![true];
+    // Parallel to `containers`: whether the value that opened that container was wrapped in an
+    // `--annotations-as` sidecar object, and so needs a closing `}` once the container itself is
+    // stepped out of (rather than right after the opening bracket, which is all we've written by
+    // the time the container's `Value`/`Null` arm runs).
+    let mut sidecar_close: Vec<bool> = vec![];
+    let mut top_level_values_written = 0usize;
+
+    if json_array {
+        write!(output, "[")?;
+    }
+
+    loop {
+        match reader.next()? {
+            StreamItem::Value(ion_type) | StreamItem::Null(ion_type) => {
+                let first = is_first.last_mut().expect("always at least one frame");
+                if *first {
+                    *first = false;
+                } else if json_array || reader.depth() > 0 {
+                    write!(output, ",")?;
+                } else {
+                    writeln!(output)?;
+                }
+
+                if reader.parent_type() == Some(IonType::Struct) {
+                    write_json_string(output, reader.field_name()?.text().unwrap_or(""))?;
+                    write!(output, ":")?;
+                }
+
+                let annotations = read_annotations_as_text(reader)?;
+                let wrap_in_sidecar = annotations_as.is_some() && !annotations.is_empty();
+                if let (true, Some(key)) = (wrap_in_sidecar, annotations_as) {
+                    write!(output, "{{")?;
+                    write_json_string(output, key)?;
+                    write!(output, ":[")?;
+                    for (i, annotation) in annotations.iter().enumerate() {
+                        if i > 0 {
+                            write!(output, ",")?;
+                        }
+                        write_json_string(output, annotation)?;
+                    }
+                    write!(output, "],\"value\":")?;
+                }
+
+                let mut opened_container = false;
+                if reader.is_null() {
+                    write!(output, "null")?;
+                } else {
+                    use IonType::*;
+                    match ion_type {
+                        Null => unreachable!("null values are handled prior to this match"),
+                        Boolean => write!(output, "{}", reader.read_bool()?)?,
+                        Integer => write!(output, "{}", reader.read_integer()?)?,
+                        Float => write_json_number(output, reader.read_f64()?)?,
+                        Decimal => write_json_decimal(output, &reader.read_decimal()?)?,
+                        Timestamp => {
+                            write_json_string(output, &reader.read_timestamp()?.to_string())?
+                        }
+                        Symbol => {
+                            write_json_string(output, reader.read_symbol()?.text().unwrap_or(""))?
+                        }
+                        String => write_json_string(output, reader.read_string()?)?,
+                        Clob => write_json_string(output, &base64_encode(reader.read_clob()?))?,
+                        Blob => write_json_string(output, &base64_encode(reader.read_blob()?))?,
+                        List | SExpression => {
+                            reader.step_in()?;
+                            write!(output, "[")?;
+                            containers.push(JsonContainer::List);
+                            is_first.push(true);
+                            sidecar_close.push(wrap_in_sidecar);
+                            opened_container = true;
+                        }
+                        Struct => {
+                            reader.step_in()?;
+                            write!(output, "{{")?;
+                            containers.push(JsonContainer::Struct);
+                            is_first.push(true);
+                            sidecar_close.push(wrap_in_sidecar);
+                            opened_container = true;
+                        }
+                    }
+                }
+
+                // For a container, the sidecar's closing `}` has to wait until the container
+                // itself is stepped out of (see the `StreamItem::Nothing` arm below); here we've
+                // only written its opening bracket so far.
+                if wrap_in_sidecar && !opened_container {
+                    write!(output, "}}")?;
+                }
+
+                if reader.depth() == 0 {
+                    top_level_values_written += 1;
+                }
+            }
+            StreamItem::Nothing if reader.depth() > 0 => {
+                reader.step_out()?;
+                is_first.pop();
+                match containers.pop() {
+                    Some(JsonContainer::List) => write!(output, "]")?,
+                    Some(JsonContainer::Struct) => write!(output, "}}")?,
+                    None => unreachable!("stepped out of a container that was never pushed"),
+                }
+                if sidecar_close.pop().unwrap_or(false) {
+                    write!(output, "}}")?;
+                }
+                if reader.depth() == 0 {
+                    top_level_values_written += 1;
+                }
+            }
+            StreamItem::Nothing => break,
+        }
+        // Back at depth 0 after a `Value`/`Null` or a step-out means a top-level value just
+        // finished; apply the `--flush-every` policy to it.
+        if flush_every != 0 && reader.depth() == 0 && top_level_values_written % flush_every == 0 {
+            output.flush()?;
+        }
+    }
+
+    if json_array {
+        write!(output, "]")?;
+    } else if top_level_values_written > 0 {
+        writeln!(output)?;
+    }
+    output.flush()?;
+    Ok(())
+}
+
+/// Collects the current value's annotations as owned strings (symbols with unknown text are
+/// dropped, matching the `--annotations-as` sidecar's "best effort" nature).
+fn read_annotations_as_text(reader: &mut Reader) -> IonResult<Vec<String>> {
+    let mut annotations = vec![];
+    if reader.has_annotations() {
+        for annotation in reader.annotations() {
+            if let Some(text) = annotation?.text() {
+                annotations.push(text.to_string());
+            }
+        }
+    }
+    Ok(annotations)
+}
+
+/// Writes a JSON string literal, escaping characters per RFC 8259.
+fn write_json_string(output: &mut Box<dyn Write>, text: &str) -> IonResult<()> {
+    write!(output, "\"")?;
+    for c in text.chars() {
+        match c {
+            '"' => write!(output, "\\\"")?,
+            '\\' => write!(output, "\\\\")?,
+            '\n' => write!(output, "\\n")?,
+            '\r' => write!(output, "\\r")?,
+            '\t' => write!(output, "\\t")?,
+            c if (c as u32) < 0x20 => write!(output, "\\u{:04x}", c as u32)?,
+            c => write!(output, "{}", c)?,
+        }
+    }
+    write!(output, "\"")?;
+    Ok(())
+}
+
+/// Writes an Ion float as a JSON number, falling back to `null` for NaN/Infinity since JSON has
+/// no representation for them.
+fn write_json_number(output: &mut Box<dyn Write>, value: f64) -> IonResult<()> {
+    if value.is_finite() {
+        write!(output, "{}", value)?;
+    } else {
+        write!(output, "null")?;
+    }
+    Ok(())
+}
+
+/// Writes an Ion decimal as a JSON number, unless its magnitude or precision would not survive
+/// an IEEE-754 round trip, in which case it's quoted as a string to avoid silently losing
+/// precision.
+///
+/// Ion's own `Display` for a decimal may use `d`/`D` for its exponent (e.g. `1.5d3`), which
+/// isn't valid JSON number syntax; normalize that to `e` (valid in both a bare JSON number and a
+/// quoted fallback string) before checking or writing anything, so the result never leaks Ion's
+/// decimal notation either way.
+fn write_json_decimal(output: &mut Box<dyn Write>, decimal: &Decimal) -> IonResult<()> {
+    let text = decimal.to_string().replace(['d', 'D'], "e");
+    let round_trips = text
+        .parse::<f64>()
+        .map(|f| f.is_finite() && f.to_string() == text)
+        .unwrap_or(false);
+    if round_trips {
+        write!(output, "{}", text)?;
+    } else {
+        write_json_string(output, &text)?;
+    }
+    Ok(())
+}
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Minimal base64 encoder (RFC 4648, with `=` padding) used for clob/blob -> JSON string
+/// down-conversion.
+fn base64_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity((bytes.len() + 2) / 3 * 4);
+    for chunk in bytes.chunks(3) {
+        let b0 = chunk[0];
+        let b1 = chunk.get(1).copied().unwrap_or(0);
+        let b2 = chunk.get(2).copied().unwrap_or(0);
+        out.push(BASE64_ALPHABET[(b0 >> 2) as usize] as char);
+        out.push(BASE64_ALPHABET[(((b0 & 0x03) << 4) | (b1 >> 4)) as usize] as char);
+        out.push(if chunk.len() > 1 {
+            BASE64_ALPHABET[(((b1 & 0x0f) << 2) | (b2 >> 6)) as usize] as char
+        } else {
+            '='
+        });
+        out.push(if chunk.len() > 2 {
+            BASE64_ALPHABET[(b2 & 0x3f) as usize] as char
+        } else {
+            '='
+        });
+    }
+    out
+}
+
+#[cfg(test)]
+mod json_tests {
+    use super::*;
+
+    #[test]
+    fn base64_encode_handles_all_padding_cases() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode(b"foob"), "Zm9vYg==");
+        assert_eq!(base64_encode(b"fooba"), "Zm9vYmE=");
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn write_json_string_escapes_control_and_special_characters() {
+        let mut output: Vec<u8> = Vec::new();
+        {
+            let mut boxed: Box<dyn Write> = Box::new(&mut output);
+            write_json_string(&mut boxed, "a\"b\\c\nd\re\tf\u{1}g").unwrap();
+        }
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "\"a\\\"b\\\\c\\nd\\re\\tf\\u0001g\""
+        );
+    }
+
+    #[test]
+    fn write_json_decimal_quotes_values_that_would_not_round_trip() {
+        let mut output: Vec<u8> = Vec::new();
+        {
+            let mut boxed: Box<dyn Write> = Box::new(&mut output);
+            write_json_decimal(&mut boxed, &Decimal::new(123, -2)).unwrap();
+        }
+        assert_eq!(String::from_utf8(output).unwrap(), "1.23");
+
+        // A decimal with more significant digits than an f64 can represent exactly must be
+        // quoted rather than silently losing precision.
+        let mut output: Vec<u8> = Vec::new();
+        {
+            let mut boxed: Box<dyn Write> = Box::new(&mut output);
+            write_json_decimal(&mut boxed, &Decimal::new(123456789012345678i64, -17)).unwrap();
+        }
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "\"1.23456789012345678\""
+        );
+    }
+
+    #[test]
+    fn write_all_as_json_ndjson_vs_array_framing() {
+        let mut reader = ReaderBuilder::new().build("1 2 3".as_bytes()).unwrap();
+        let mut bytes: Vec<u8> = Vec::new();
+        {
+            let mut boxed: Box<dyn Write> = Box::new(&mut bytes);
+            write_all_as_json(&mut reader, &mut boxed, false, None, 0).unwrap();
+        }
+        assert_eq!(String::from_utf8(bytes).unwrap(), "1\n2\n3\n");
+
+        let mut reader = ReaderBuilder::new().build("1 2 3".as_bytes()).unwrap();
+        let mut bytes: Vec<u8> = Vec::new();
+        {
+            let mut boxed: Box<dyn Write> = Box::new(&mut bytes);
+            write_all_as_json(&mut reader, &mut boxed, true, None, 0).unwrap();
+        }
+        assert_eq!(String::from_utf8(bytes).unwrap(), "[1,2,3]");
+    }
+
+    #[test]
+    fn write_all_as_json_closes_sidecar_after_an_annotated_container() {
+        let mut reader = ReaderBuilder::new().build("foo::[1,2]".as_bytes()).unwrap();
+        let mut bytes: Vec<u8> = Vec::new();
+        {
+            let mut boxed: Box<dyn Write> = Box::new(&mut bytes);
+            write_all_as_json(&mut reader, &mut boxed, false, Some("ann"), 0).unwrap();
+        }
+        assert_eq!(
+            String::from_utf8(bytes).unwrap(),
+            "{\"ann\":[\"foo\"],\"value\":[1,2]}\n"
+        );
+    }
+
+    #[test]
+    fn write_json_decimal_normalizes_ion_exponent_notation() {
+        let mut output: Vec<u8> = Vec::new();
+        {
+            let mut boxed: Box<dyn Write> = Box::new(&mut output);
+            write_json_decimal(&mut boxed, &Decimal::new(15, 2)).unwrap();
+        }
+        // Whether this round-trips as a bare number or gets quoted, it must never contain Ion's
+        // `d` exponent marker.
+        assert!(!String::from_utf8(output).unwrap().contains('d'));
+    }
+}